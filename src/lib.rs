@@ -1,26 +1,233 @@
 #![allow(non_snake_case)]
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
-use serde::{Deserialize, Serialize};
-use syn::parse::Parser;
-use syn::Stmt;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::{parenthesized, Ident, Stmt, Token, Type};
 use syn::{parse_macro_input, ItemStruct};
 use syn::{ImplItem, ItemImpl};
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+/// A single `name: type` entry inside `port(output(...))`.
+struct OutputPortDef {
+    name: Ident,
+    ty: Type,
+}
+
+impl Parse for OutputPortDef {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ty: Type = input.parse()?;
+        Ok(OutputPortDef { name, ty })
+    }
+}
+
+/// How a freshly-received wire value is coerced into the component's internal
+/// representation, e.g. `a: Word => u8(truncate)`.
+struct PortConversion {
+    internal_ty: Type,
+    /// One of `truncate`, `sign_extend`, `zero_extend`, `reinterpret`; carries
+    /// the span of the name so an unknown kind points at the right token.
+    kind: Ident,
+}
+
+const CONVERSION_KINDS: &[&str] = &["truncate", "sign_extend", "zero_extend", "reinterpret"];
+
+/// A single `name: wire_type [=> internal_type(conversion)]` entry inside
+/// `port(input(...))`.
+struct InputPortDef {
+    name: Ident,
+    wire_ty: Type,
+    conversion: Option<PortConversion>,
+}
+
+impl Parse for InputPortDef {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let wire_ty: Type = input.parse()?;
+
+        let conversion = if input.peek(Token![=>]) {
+            input.parse::<Token![=>]>()?;
+            // Parsed as a `Type`, a path directly followed by `(...)` would take the
+            // parentheses as `Fn`-style arguments (`u8(truncate)`), so path types are
+            // parsed as a bare `Path` to leave the conversion kind in place.
+            let internal_ty: Type = if input.peek(Ident) || input.peek(Token![::]) {
+                Type::Path(syn::TypePath {
+                    qself: None,
+                    path: input.parse()?,
+                })
+            } else {
+                input.parse()?
+            };
+            let content;
+            parenthesized!(content in input);
+            let kind: Ident = content.parse()?;
+            if !CONVERSION_KINDS.contains(&kind.to_string().as_str()) {
+                return Err(syn::Error::new(
+                    kind.span(),
+                    format!(
+                        "unknown conversion `{kind}`, expected one of {}",
+                        CONVERSION_KINDS.join(", ")
+                    ),
+                ));
+            }
+            Some(PortConversion { internal_ty, kind })
+        } else {
+            None
+        };
+
+        Ok(InputPortDef {
+            name,
+            wire_ty,
+            conversion,
+        })
+    }
+}
+
+#[derive(Default)]
 struct ComponentConfig {
     port: Option<ComponentPortConfig>,
-    // serde default on bool is false
-    #[serde(default)]
     is_primary: bool,
+    /// `async` flag: generate an awaited `run` loop instead of polling `poll_recv`.
+    is_async: bool,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+/// Parses the attribute meta, e.g.
+/// `port(input(a: u8, b: Word), output(y: u8), clock), is_primary, async`
+impl Parse for ComponentConfig {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut config = ComponentConfig::default();
+        let mut seen_port = false;
+        let mut seen_is_primary = false;
+        let mut seen_is_async = false;
+
+        while !input.is_empty() {
+            // `async` is a keyword, so accept keyword idents here too.
+            let ident = Ident::parse_any(input)?;
+            match ident.to_string().as_str() {
+                "port" => {
+                    if seen_port {
+                        return Err(syn::Error::new(ident.span(), "duplicate `port` entry"));
+                    }
+                    seen_port = true;
+                    let content;
+                    parenthesized!(content in input);
+                    config.port = Some(content.parse::<ComponentPortConfig>()?);
+                }
+                "is_primary" => {
+                    if seen_is_primary {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "duplicate `is_primary` flag",
+                        ));
+                    }
+                    seen_is_primary = true;
+                    config.is_primary = true;
+                }
+                "async" => {
+                    if seen_is_async {
+                        return Err(syn::Error::new(ident.span(), "duplicate `async` flag"));
+                    }
+                    seen_is_async = true;
+                    config.is_async = true;
+                }
+                other => {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        format!(
+                            "unknown component attribute `{other}`, expected `port`, `is_primary` or `async`"
+                        ),
+                    ));
+                }
+            }
+
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            } else {
+                break;
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+#[derive(Default)]
 struct ComponentPortConfig {
-    input: Option<Vec<(String, String)>>,
-    output: Option<Vec<(String, String)>>,
-    #[serde(default)]
-    clock: bool,
+    input: Option<Vec<InputPortDef>>,
+    output: Option<Vec<OutputPortDef>>,
+    /// Clock domain names, e.g. `clock(fast, slow)`. An empty vec means the
+    /// bare `clock` shorthand: a single unnamed domain.
+    clock: Option<Vec<Ident>>,
+}
+
+/// Parses the body of `port(...)`, e.g.
+/// `input(a: u8, b: Word => u8(truncate)), output(y: u8), clock(fast, slow)`
+impl Parse for ComponentPortConfig {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut config = ComponentPortConfig::default();
+        let mut seen_input = false;
+        let mut seen_output = false;
+        let mut seen_clock = false;
+
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            match ident.to_string().as_str() {
+                "input" => {
+                    if seen_input {
+                        return Err(syn::Error::new(ident.span(), "duplicate `input` entry"));
+                    }
+                    seen_input = true;
+                    let content;
+                    parenthesized!(content in input);
+                    let ports = content.parse_terminated(InputPortDef::parse, Token![,])?;
+                    config.input = Some(ports.into_iter().collect());
+                }
+                "output" => {
+                    if seen_output {
+                        return Err(syn::Error::new(ident.span(), "duplicate `output` entry"));
+                    }
+                    seen_output = true;
+                    let content;
+                    parenthesized!(content in input);
+                    let ports = content.parse_terminated(OutputPortDef::parse, Token![,])?;
+                    config.output = Some(ports.into_iter().collect());
+                }
+                "clock" => {
+                    if seen_clock {
+                        return Err(syn::Error::new(ident.span(), "duplicate `clock` entry"));
+                    }
+                    seen_clock = true;
+                    if input.peek(syn::token::Paren) {
+                        let content;
+                        parenthesized!(content in input);
+                        let domains = content.parse_terminated(Ident::parse, Token![,])?;
+                        config.clock = Some(domains.into_iter().collect());
+                    } else {
+                        // Bare `clock`: shorthand for a single unnamed domain.
+                        config.clock = Some(Vec::new());
+                    }
+                }
+                other => {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        format!(
+                            "unknown port attribute `{other}`, expected `input`, `output` or `clock`"
+                        ),
+                    ));
+                }
+            }
+
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            } else {
+                break;
+            }
+        }
+
+        Ok(config)
+    }
 }
 
 /// Preprocessor for a component
@@ -32,19 +239,43 @@ struct ComponentPortConfig {
 /// 4. `${port_name}_{receiver/sender}: Input/Output`
 /// 5. `${port_name}: ${port_type}`
 /// 6. `${port_name}_old: ${port_type}` to prevent circular dependency
-/// 7. `clock_sender: Output` and `clock_receiver: Input` if the component has a clock
+/// 7. `clock_sender: Output` and `clock_receiver: Input` if the component has an unnamed clock,
+///    or `clock_${domain}_sender: Output` and `clock_${domain}_receiver: Input` per named domain
 ///
 /// For each port, the proc macro will also generate an implementation of `poll_recv`
 /// - The data will be extracted from the received event from `${port_name}_{receiver}` and put into `${port_name}`
+/// - If the input port declares a conversion (`name: wire_ty => internal_ty(kind)`), the wire
+///   value is run through `rsim_core::convert::Convert` into `${port_name}_converted` first
 /// - `on_comb` will be invoked
-/// - If the port is clock, `on_clock` will also be invoked prior to `on_comb`
+/// - If the port is clock, `on_clock` (or `on_clock_${domain}` for a named domain) will also be
+///   invoked prior to `on_comb`
+///
+/// The config is written as attribute meta, not a JSON blob, e.g.
+/// `#[ComponentAttribute(port(input(a: u8, b: Word), output(y: u8), clock(fast, slow)), is_primary)]`
 ///
 /// User should write the impl for the following functions:
 /// - `init_impl(&mut self)`
 /// - `reset_impl(&mut self)`
 /// - `poll_impl(&mut self)`
 /// - `on_comb(&mut self)`
-/// - `on_clock(&mut self)`
+/// - `on_clock(&mut self)`, or `on_clock_${domain}(&mut self)` per named clock domain
+///
+/// With the `async` flag set, `poll_recv` is left untouched (it still just calls
+/// `poll_impl`) and a `run(&mut self) -> Pin<Box<dyn Future<Output = ()> + '_>>`
+/// is generated instead: it loops, awaiting all input/clock receivers
+/// select-style, dispatching `on_comb`/`on_clock` the same way the sync path
+/// does. `run` can't be an `async fn` on the `Component` trait object (async
+/// fns aren't object-safe), so it's exposed through the small, object-safe
+/// `AsyncComponent` trait instead, which `sim_manager` can call on the
+/// boxed/dyn component it already holds. `init` registers the task id with
+/// `sim_manager` so it knows to drive this component via `AsyncComponent::run`
+/// instead of polling `poll_recv` every tick.
+///
+/// Before any tokens are emitted, the port table is validated: duplicate port
+/// names (including across input/output), and ports colliding with a reserved
+/// generated field name (`component_id`, `sim_manager`, `ack_sender`, or the
+/// clock sender/receiver fields) are rejected with a `compile_error!` naming
+/// the offending port.
 #[proc_macro_attribute]
 pub fn ComponentAttribute(config: TokenStream, input: TokenStream) -> TokenStream {
     let mut item_struct = parse_macro_input!(input as ItemStruct);
@@ -65,7 +296,12 @@ pub fn ComponentAttribute(config: TokenStream, input: TokenStream) -> TokenStrea
 
     let mut component_impl_item = parse_macro_input!(component_impl_ts as ItemImpl);
 
-    let component_config: ComponentConfig = serde_json::from_str(&config.to_string()).unwrap();
+    let component_config = parse_macro_input!(config as ComponentConfig);
+    if let Err(err) = validate_component_config(&component_config) {
+        return err.to_compile_error().into();
+    }
+    let is_async = component_config.is_async;
+    let mut async_arms: Vec<proc_macro2::TokenStream> = Vec::new();
 
     // Every component should have these values
     let mut extended_field = vec![
@@ -95,32 +331,47 @@ pub fn ComponentAttribute(config: TokenStream, input: TokenStream) -> TokenStrea
     }
 
     if let Some(port) = component_config.port {
-        // If the component has clock, we need to
+        // If the component has clock domains, for each one we need to
         // 1. register the clock with the sim manager
-        // 2. call on_clock when clock ticks
-        if port.clock {
-            extended_field.extend(vec![
-                syn::Field::parse_named
-                    .parse2(quote! { clock_sender: Output })
-                    .unwrap(),
-                syn::Field::parse_named
-                    .parse2(quote! { clock_receiver: Input })
-                    .unwrap(),
-            ]);
-            let _ = component_impl_item
-                .items
-                .iter_mut()
-                .map(|item| {
-                    if let ImplItem::Fn(func) = item {
-                        if func.sig.ident == format_ident!("init") {
-                            func.block.stmts.push(syn::parse_quote! {self.sim_manager
-                            .register_clock_tick(self.clock_sender.clone());})
-                        } else if func.sig.ident == format_ident!("poll_recv") {
-                            push_clock_recv_stmt(&mut func.block.stmts)
+        // 2. call on_clock (or on_clock_${domain}) when that domain ticks
+        if let Some(domains) = port.clock {
+            // An empty list is the bare `clock` shorthand: one unnamed domain.
+            let domains: Vec<Option<Ident>> = if domains.is_empty() {
+                vec![None]
+            } else {
+                domains.into_iter().map(Some).collect()
+            };
+
+            for domain in &domains {
+                let (sender_field, receiver_field) = clock_field_idents(domain.as_ref());
+
+                extended_field.extend(vec![
+                    syn::Field::parse_named
+                        .parse2(quote! { #sender_field: Output })
+                        .unwrap(),
+                    syn::Field::parse_named
+                        .parse2(quote! { #receiver_field: Input })
+                        .unwrap(),
+                ]);
+                let _ = component_impl_item
+                    .items
+                    .iter_mut()
+                    .map(|item| {
+                        if let ImplItem::Fn(func) = item {
+                            if func.sig.ident == format_ident!("init") {
+                                func.block.stmts.push(syn::parse_quote! {self.sim_manager
+                                .register_clock_tick(self.#sender_field.clone());})
+                            } else if !is_async && func.sig.ident == format_ident!("poll_recv") {
+                                push_clock_recv_stmt(&mut func.block.stmts, domain.as_ref())
+                            }
                         }
-                    }
-                })
-                .collect::<Vec<_>>();
+                    })
+                    .collect::<Vec<_>>();
+
+                if is_async {
+                    async_arms.push(clock_recv_arm(domain.as_ref()));
+                }
+            }
         }
         // For each input port, it will have
         // 1. a mpsc receiver
@@ -129,25 +380,40 @@ pub fn ComponentAttribute(config: TokenStream, input: TokenStream) -> TokenStrea
         port.input.map(|input| {
             input
                 .iter()
-                .map(|(port_name, port_type)| {
-                    let rx = format_ident!("{}", port_name);
-                    let rx_type: proc_macro2::TokenStream = port_type.parse().unwrap();
+                .map(|port_def| {
+                    let rx = &port_def.name;
+                    let wire_ty = &port_def.wire_ty;
                     extended_field.extend(vec![syn::Field::parse_named
-                        .parse2(quote! { pub #rx: rsim_core::rx::Rx<#rx_type> })
+                        .parse2(quote! { pub #rx: rsim_core::rx::Rx<#wire_ty> })
                         .unwrap()]);
+                    if let Some(conversion) = &port_def.conversion {
+                        let converted_field = converted_field_ident(rx);
+                        let internal_ty = &conversion.internal_ty;
+                        extended_field.extend(vec![syn::Field::parse_named
+                            .parse2(quote! { pub #converted_field: #internal_ty })
+                            .unwrap()]);
+                    }
                     let _ = component_impl_item
                         .items
                         .iter_mut()
                         .map(|item| {
                             if let ImplItem::Fn(func) = item {
-                                if func.sig.ident == format_ident!("poll_recv") {
-                                    push_comb_recv_stmt(&mut func.block.stmts, port_name)
+                                if !is_async && func.sig.ident == format_ident!("poll_recv") {
+                                    push_comb_recv_stmt(
+                                        &mut func.block.stmts,
+                                        rx,
+                                        port_def.conversion.as_ref(),
+                                    )
                                 } else if func.sig.ident == format_ident!("reset") {
-                                    push_reset_stmt(&mut func.block.stmts, port_name)
+                                    push_reset_stmt(&mut func.block.stmts, rx)
                                 }
                             }
                         })
                         .collect::<Vec<_>>();
+
+                    if is_async {
+                        async_arms.push(comb_recv_arm(rx, port_def.conversion.as_ref()));
+                    }
                 })
                 .collect::<Vec<_>>()
         });
@@ -155,9 +421,9 @@ pub fn ComponentAttribute(config: TokenStream, input: TokenStream) -> TokenStrea
         port.output.map(|output| {
             output
                 .iter()
-                .map(|(port_name, port_type)| {
-                    let tx = format_ident!("{}", port_name);
-                    let tx_type: proc_macro2::TokenStream = port_type.parse().unwrap();
+                .map(|port_def| {
+                    let tx = &port_def.name;
+                    let tx_type = &port_def.ty;
                     extended_field.extend(vec![syn::Field::parse_named
                         .parse2(quote! { pub #tx: rsim_core::tx::Tx<#tx_type> })
                         .unwrap()])
@@ -166,6 +432,42 @@ pub fn ComponentAttribute(config: TokenStream, input: TokenStream) -> TokenStrea
         });
     };
 
+    let run_impl = if is_async {
+        let _ = component_impl_item
+            .items
+            .iter_mut()
+            .map(|item| {
+                if let ImplItem::Fn(func) = item {
+                    if func.sig.ident == format_ident!("init") {
+                        func.block.stmts.push(syn::parse_quote! {
+                            self.sim_manager.register_task(self.get_component_id());
+                        })
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // `run` can't live on `Component` (async fns aren't object-safe), so it's
+        // exposed through `AsyncComponent` instead: a small, object-safe trait
+        // `sim_manager` can actually call `run` through on the `dyn Component` it
+        // already holds for this registered task.
+        Some(quote! {
+            impl AsyncComponent for #struct_name {
+                fn run(&mut self) -> Pin<Box<dyn Future<Output = ()> + '_>> {
+                    Box::pin(async move {
+                        loop {
+                            rsim_core::select! {
+                                #(#async_arms),*
+                            }
+                        }
+                    })
+                }
+            }
+        })
+    } else {
+        None
+    };
+
     if let syn::Fields::Named(ref mut fields) = item_struct.fields {
         fields.named.extend(extended_field);
     }
@@ -177,28 +479,155 @@ pub fn ComponentAttribute(config: TokenStream, input: TokenStream) -> TokenStrea
         #item_struct
 
         #component_impl_item
+
+        #run_impl
     })
     .into()
 }
 
-fn push_clock_recv_stmt(stmt: &mut Vec<Stmt>) {
-    let receiver = format_ident!("clock_receiver");
+/// Struct fields every component gets regardless of its port table.
+const RESERVED_FIELD_NAMES: &[&str] = &["component_id", "sim_manager", "ack_sender"];
+
+/// Walks the parsed `ComponentConfig` before any tokens are emitted, catching
+/// port-table mistakes that would otherwise surface as a confusing type error
+/// deep in the generated struct/impl. Empty/non-identifier port names and
+/// unparseable port types are already rejected by `syn` while parsing the
+/// config itself; this covers what's left: duplicate names and collisions
+/// with the fields the macro always generates, including the synthesized
+/// `{name}_converted` field an input port with a conversion gets alongside
+/// its `Rx` field.
+fn validate_component_config(config: &ComponentConfig) -> syn::Result<()> {
+    let Some(port) = &config.port else {
+        return Ok(());
+    };
+
+    let mut reserved: std::collections::HashSet<String> =
+        RESERVED_FIELD_NAMES.iter().map(|s| s.to_string()).collect();
+
+    if let Some(domains) = &port.clock {
+        let mut seen_domains = std::collections::HashSet::new();
+        for domain in domains {
+            if !seen_domains.insert(domain.to_string()) {
+                return Err(syn::Error::new(
+                    domain.span(),
+                    format!("duplicate clock domain `{domain}`"),
+                ));
+            }
+        }
+
+        // An empty list is the bare `clock` shorthand: one unnamed domain.
+        let domains: Vec<Option<&Ident>> = if domains.is_empty() {
+            vec![None]
+        } else {
+            domains.iter().map(Some).collect()
+        };
+        for domain in domains {
+            let (sender, receiver) = clock_field_idents(domain);
+            reserved.insert(sender.to_string());
+            reserved.insert(receiver.to_string());
+        }
+    }
+
+    // Each input port contributes its own field name, plus (if it declares a
+    // conversion) the synthesized `{name}_converted` field generated alongside
+    // it, since that field can collide just as easily as a literal port name.
+    let mut candidate_fields: Vec<Ident> = Vec::new();
+    for input in port.input.iter().flatten() {
+        candidate_fields.push(input.name.clone());
+        if input.conversion.is_some() {
+            candidate_fields.push(converted_field_ident(&input.name));
+        }
+    }
+    for output in port.output.iter().flatten() {
+        candidate_fields.push(output.name.clone());
+    }
+
+    let mut seen_ports: std::collections::HashMap<String, Ident> = std::collections::HashMap::new();
+    for name in &candidate_fields {
+        let key = name.to_string();
+        if reserved.contains(&key) {
+            return Err(syn::Error::new(
+                name.span(),
+                format!("port `{key}` collides with a reserved generated field name"),
+            ));
+        }
+        if seen_ports.contains_key(&key) {
+            return Err(syn::Error::new(
+                name.span(),
+                format!("duplicate port name `{key}`"),
+            ));
+        }
+        seen_ports.insert(key, name.clone());
+    }
+
+    Ok(())
+}
+
+/// Field names for a clock domain's sender/receiver pair. `None` is the unnamed domain.
+fn clock_field_idents(domain: Option<&Ident>) -> (Ident, Ident) {
+    match domain {
+        Some(name) => (
+            format_ident!("clock_{}_sender", name),
+            format_ident!("clock_{}_receiver", name),
+        ),
+        None => (format_ident!("clock_sender"), format_ident!("clock_receiver")),
+    }
+}
+
+fn push_clock_recv_stmt(stmt: &mut Vec<Stmt>, domain: Option<&Ident>) {
+    let (_, receiver) = clock_field_idents(domain);
+    let on_clock = match domain {
+        Some(name) => format_ident!("on_clock_{}", name),
+        None => format_ident!("on_clock"),
+    };
 
     stmt.push(syn::parse_quote! {
         if let Ok(event) = self.#receiver.try_recv() {
-            self.on_clock();
+            self.#on_clock();
             self.on_comb();
             self.ack_sender.send(event.get_event_id()).unwrap();
         }
     })
 }
 
-fn push_comb_recv_stmt(stmt: &mut Vec<Stmt>, port_name: &str) {
-    let rx = format_ident!("{}", port_name);
+/// Field name holding a port's converted, internal-representation value.
+fn converted_field_ident(rx: &Ident) -> Ident {
+    format_ident!("{}_converted", rx)
+}
 
+/// `snake_case` -> `PascalCase`, e.g. `sign_extend` -> `SignExtend`, to name the
+/// matching `rsim_core::convert::Conversion` variant.
+fn to_pascal_case(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn push_comb_recv_stmt(stmt: &mut Vec<Stmt>, rx: &Ident, conversion: Option<&PortConversion>) {
     stmt.push(syn::parse_quote! {
         let recv_result = self.#rx.try_recv();
     });
+
+    if let Some(conversion) = conversion {
+        let converted_field = converted_field_ident(rx);
+        let internal_ty = &conversion.internal_ty;
+        let kind = format_ident!("{}", to_pascal_case(&conversion.kind.to_string()));
+        stmt.push(syn::parse_quote! {
+            if recv_result == rsim_core::rx::RxType::NewValue {
+                self.#converted_field = rsim_core::convert::Convert::<#internal_ty>::convert(
+                    &self.#rx,
+                    rsim_core::convert::Conversion::#kind,
+                );
+            }
+        });
+    }
+
     stmt.push(syn::parse_quote! {
         if recv_result == rsim_core::rx::RxType::NewValue {
             self.on_comb();
@@ -211,10 +640,54 @@ fn push_comb_recv_stmt(stmt: &mut Vec<Stmt>, port_name: &str) {
     });
 }
 
-fn push_reset_stmt(stmt: &mut Vec<Stmt>, port_name: &str) {
-    let rx = format_ident!("{}", port_name);
-
+fn push_reset_stmt(stmt: &mut Vec<Stmt>, rx: &Ident) {
     stmt.push(syn::parse_quote! {
         self.#rx.reset();
     });
 }
+
+/// `rsim_core::select!` arm awaiting one clock domain's receiver, for async `run`.
+fn clock_recv_arm(domain: Option<&Ident>) -> proc_macro2::TokenStream {
+    let (_, receiver) = clock_field_idents(domain);
+    let on_clock = match domain {
+        Some(name) => format_ident!("on_clock_{}", name),
+        None => format_ident!("on_clock"),
+    };
+
+    quote! {
+        Ok(event) = self.#receiver.recv() => {
+            self.#on_clock();
+            self.on_comb();
+            self.ack_sender.send(event.get_event_id()).unwrap();
+        }
+    }
+}
+
+/// `rsim_core::select!` arm awaiting one input port's receiver, for async `run`.
+fn comb_recv_arm(rx: &Ident, conversion: Option<&PortConversion>) -> proc_macro2::TokenStream {
+    let conversion_stmt = conversion.map(|conversion| {
+        let converted_field = converted_field_ident(rx);
+        let internal_ty = &conversion.internal_ty;
+        let kind = format_ident!("{}", to_pascal_case(&conversion.kind.to_string()));
+        quote! {
+            if recv_result == rsim_core::rx::RxType::NewValue {
+                self.#converted_field = rsim_core::convert::Convert::<#internal_ty>::convert(
+                    &self.#rx,
+                    rsim_core::convert::Conversion::#kind,
+                );
+            }
+        }
+    });
+
+    quote! {
+        recv_result = self.#rx.recv() => {
+            #conversion_stmt
+            if recv_result == rsim_core::rx::RxType::NewValue {
+                self.on_comb();
+            }
+            if recv_result != rsim_core::rx::RxType::NoValue {
+                self.#rx.ack();
+            }
+        }
+    }
+}
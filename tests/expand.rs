@@ -0,0 +1,9 @@
+//! Snapshot tests for `ComponentAttribute`'s happy-path expansion shape.
+//! These compare the macro's generated tokens against a committed
+//! `.expanded.rs`, so a change to the generated struct/impl layout is a
+//! visible diff here instead of only surfacing as a downstream type error.
+
+#[test]
+fn expand() {
+    macrotest::expand("tests/expand/*.rs");
+}
@@ -0,0 +1,47 @@
+use rsim_macro::ComponentAttribute;
+struct Adder {
+    component_id: rsim_core::types::ComponentId,
+    sim_manager: Arc<SimManager>,
+    ack_sender: crossbeam_channel::Sender<EventId>,
+    clock_sender: Output,
+    clock_receiver: Input,
+    pub a: rsim_core::rx::Rx<u8>,
+    pub y: rsim_core::tx::Tx<u8>,
+}
+impl Component for Adder {
+    fn init(&mut self) {
+        self.init_impl();
+        self.sim_manager.register_do_not_end(self.get_component_id());
+        self.sim_manager.register_clock_tick(self.clock_sender.clone());
+    }
+    fn reset(&mut self) {
+        self.reset_impl();
+        self.a.reset();
+    }
+    fn poll_recv(&mut self) {
+        self.poll_impl();
+        if let Ok(event) = self.clock_receiver.try_recv() {
+            self.on_clock();
+            self.on_comb();
+            self.ack_sender.send(event.get_event_id()).unwrap();
+        }
+        let recv_result = self.a.try_recv();
+        if recv_result == rsim_core::rx::RxType::NewValue {
+            self.on_comb();
+        }
+        if recv_result != rsim_core::rx::RxType::NoValue {
+            self.a.ack();
+        }
+    }
+    fn get_component_id(&self) -> ComponentId {
+        self.component_id
+    }
+}
+impl Adder {
+    fn init_impl(&mut self) {}
+    fn reset_impl(&mut self) {}
+    fn poll_impl(&mut self) {}
+    fn on_comb(&mut self) {}
+    fn on_clock(&mut self) {}
+}
+fn main() {}
@@ -0,0 +1,14 @@
+use rsim_macro::ComponentAttribute;
+
+#[ComponentAttribute(port(input(a: u8), output(y: u8), clock), is_primary)]
+struct Adder {}
+
+impl Adder {
+    fn init_impl(&mut self) {}
+    fn reset_impl(&mut self) {}
+    fn poll_impl(&mut self) {}
+    fn on_comb(&mut self) {}
+    fn on_clock(&mut self) {}
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+//! UI tests for `ComponentAttribute`'s diagnostics: these assert the exact
+//! `compile_error!`/`syn::Error` text and span the macro produces for each
+//! invalid config, so a regression in a diagnostic's wording or location is
+//! caught here instead of only showing up as a confusing downstream error.
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
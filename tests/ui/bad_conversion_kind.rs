@@ -0,0 +1,6 @@
+use rsim_macro::ComponentAttribute;
+
+#[ComponentAttribute(port(input(a: Word => u8(widen))))]
+struct BadConversion {}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use rsim_macro::ComponentAttribute;
+
+#[ComponentAttribute(port(input(a: Word => u8(truncate), a_converted: u8)))]
+struct ConvertedCollision {}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use rsim_macro::ComponentAttribute;
+
+#[ComponentAttribute(is_primary, is_primary)]
+struct Duplicated {}
+
+fn main() {}
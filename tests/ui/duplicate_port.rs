@@ -0,0 +1,6 @@
+use rsim_macro::ComponentAttribute;
+
+#[ComponentAttribute(port(input(a: u8), output(a: u8)))]
+struct DuplicatePort {}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use rsim_macro::ComponentAttribute;
+
+#[ComponentAttribute(port(input(sim_manager: u8)))]
+struct ReservedName {}
+
+fn main() {}